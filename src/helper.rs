@@ -0,0 +1,165 @@
+use std::borrow::Cow::{self, Owned};
+use std::cell::Cell;
+
+use rustyline::completion::{Completer, Pair};
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::{ValidationContext, ValidationResult, Validator};
+use rustyline::{Context, Helper};
+
+use crate::operations::Operations;
+
+const COMMANDS: &[&str] = &[
+    "s", "m", "r", "show", "help", "h", "clear", "c", "restart", "reduce", "rref", "undo", "redo",
+    "t", "transpose", "mul", "det", "determinant", "inv", "inverse", "save", "load", "run",
+    "exit", "q",
+];
+
+pub struct CalcHelper {
+    height: Cell<usize>,
+}
+
+impl CalcHelper {
+    pub fn new() -> Self {
+        Self {
+            height: Cell::new(0),
+        }
+    }
+
+    pub fn set_height(&self, height: usize) {
+        self.height.set(height);
+    }
+}
+
+fn current_word(line: &str, pos: usize) -> (usize, &str) {
+    let start = line[..pos].rfind(' ').map(|i| i + 1).unwrap_or(0);
+    (start, &line[start..pos])
+}
+
+impl Completer for CalcHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let (start, word) = current_word(line, pos);
+        let is_first_word = line[..start].trim().is_empty();
+
+        let candidates: Vec<Pair> = if is_first_word {
+            COMMANDS
+                .iter()
+                .filter(|c| c.starts_with(word))
+                .map(|c| Pair {
+                    display: c.to_string(),
+                    replacement: c.to_string(),
+                })
+                .collect()
+        } else {
+            (0..self.height.get())
+                .map(|i| format!("r{}", i))
+                .filter(|r| r.starts_with(word))
+                .map(|r| Pair {
+                    display: r.clone(),
+                    replacement: r,
+                })
+                .collect()
+        };
+
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for CalcHelper {
+    type Hint = String;
+
+    fn hint(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> Option<String> {
+        if pos < line.len() {
+            return None;
+        }
+
+        let mut words = line.split_whitespace();
+        let command = words.next()?.to_lowercase();
+        let remaining_args = words.count();
+
+        let shape = match command.as_str() {
+            "s" => " (row1) (row2)",
+            "m" => " (scaler expr) (row)",
+            "r" => " (scaler expr) (scaler row) (target row)",
+            "save" | "load" | "run" => " (path)",
+            _ => return None,
+        };
+
+        if remaining_args > 0 && !line.ends_with(' ') {
+            return None;
+        }
+
+        Some(shape.to_string())
+    }
+}
+
+impl Highlighter for CalcHelper {
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+        let mut out = String::with_capacity(line.len());
+
+        for (i, word) in line.split(' ').enumerate() {
+            if i > 0 {
+                out.push(' ');
+            }
+
+            if word.is_empty() {
+                continue;
+            }
+
+            if i == 0 {
+                out.push_str(&format!("\x1b[1;36m{}\x1b[0m", word));
+            } else if word.len() > 1
+                && (word.starts_with('r') || word.starts_with('R'))
+                && word[1..].bytes().all(|b| b.is_ascii_digit())
+            {
+                out.push_str(&format!("\x1b[32m{}\x1b[0m", word));
+            } else if word
+                .chars()
+                .all(|c| c.is_ascii_digit() || "+-*/().".contains(c))
+            {
+                out.push_str(&format!("\x1b[33m{}\x1b[0m", word));
+            } else {
+                out.push_str(word);
+            }
+        }
+
+        Owned(out)
+    }
+
+    fn highlight_char(&self, _line: &str, _pos: usize, _forced: bool) -> bool {
+        true
+    }
+}
+
+impl Validator for CalcHelper {
+    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        let input = ctx.input();
+
+        if input.trim().is_empty() {
+            return Ok(ValidationResult::Valid(None));
+        }
+
+        match Operations::try_from(input) {
+            Ok(_) => Ok(ValidationResult::Valid(None)),
+            // Only hold the line open for a genuinely incomplete instruction
+            // (e.g. an unmatched paren); outright syntax errors should fall
+            // through to the main loop's error echo and a fresh prompt,
+            // rather than trapping the user in an unsubmittable buffer.
+            // ("got an empty line" can't occur here: it only fires on
+            // all-whitespace input, already handled by the check above.)
+            Err(e) if e.contains("incomplete instruction") => {
+                Ok(ValidationResult::Invalid(Some(format!(" - {}", e))))
+            }
+            Err(_) => Ok(ValidationResult::Valid(None)),
+        }
+    }
+}
+
+impl Helper for CalcHelper {}
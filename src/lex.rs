@@ -0,0 +1,154 @@
+use std::fmt::{self, Formatter};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: (usize, usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    Number(i64),
+    /// An `r`/`R` immediately followed by digits, e.g. `r1`.
+    Row(usize),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    LParen,
+    RParen,
+}
+
+pub struct LexError {
+    pub message: String,
+    pub span: (usize, usize),
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (at byte {}..{})",
+            self.message, self.span.0, self.span.1
+        )
+    }
+}
+
+pub fn lex(input: &str) -> Result<Vec<Token>, LexError> {
+    let mut chars = input.char_indices().peekable();
+    let mut tokens = Vec::new();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        match c {
+            '+' => {
+                tokens.push(Token {
+                    kind: TokenKind::Plus,
+                    span: (start, start + c.len_utf8()),
+                });
+                chars.next();
+            }
+            '-' => {
+                tokens.push(Token {
+                    kind: TokenKind::Minus,
+                    span: (start, start + c.len_utf8()),
+                });
+                chars.next();
+            }
+            '*' => {
+                tokens.push(Token {
+                    kind: TokenKind::Star,
+                    span: (start, start + c.len_utf8()),
+                });
+                chars.next();
+            }
+            '/' => {
+                tokens.push(Token {
+                    kind: TokenKind::Slash,
+                    span: (start, start + c.len_utf8()),
+                });
+                chars.next();
+            }
+            '(' => {
+                tokens.push(Token {
+                    kind: TokenKind::LParen,
+                    span: (start, start + c.len_utf8()),
+                });
+                chars.next();
+            }
+            ')' => {
+                tokens.push(Token {
+                    kind: TokenKind::RParen,
+                    span: (start, start + c.len_utf8()),
+                });
+                chars.next();
+            }
+            c if c.is_ascii_digit() => {
+                let mut end = start + c.len_utf8();
+                chars.next();
+                while let Some(&(i, c)) = chars.peek() {
+                    if !c.is_ascii_digit() {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+
+                let text = &input[start..end];
+                let n = text.parse::<i64>().map_err(|_| LexError {
+                    message: format!("\"{}\" is not a valid number", text),
+                    span: (start, end),
+                })?;
+                tokens.push(Token {
+                    kind: TokenKind::Number(n),
+                    span: (start, end),
+                });
+            }
+            c if c.is_alphabetic() => {
+                let mut end = start + c.len_utf8();
+                chars.next();
+                while let Some(&(i, c)) = chars.peek() {
+                    if !c.is_alphanumeric() {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+
+                let text = &input[start..end];
+
+                if (c == 'r' || c == 'R')
+                    && text.len() > 1
+                    && text[1..].bytes().all(|b| b.is_ascii_digit())
+                {
+                    let n = text[1..].parse::<usize>().map_err(|_| LexError {
+                        message: format!("\"{}\" is not a valid row reference", text),
+                        span: (start, end),
+                    })?;
+                    tokens.push(Token {
+                        kind: TokenKind::Row(n),
+                        span: (start, end),
+                    });
+                } else {
+                    tokens.push(Token {
+                        kind: TokenKind::Ident(text.to_string()),
+                        span: (start, end),
+                    });
+                }
+            }
+            c => {
+                return Err(LexError {
+                    message: format!("Unexpected character '{}'", c),
+                    span: (start, start + c.len_utf8()),
+                });
+            }
+        }
+    }
+
+    Ok(tokens)
+}
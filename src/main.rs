@@ -2,53 +2,30 @@ use std::io::{stdin, stdout, Write};
 use std::str::FromStr;
 
 use fraction::{Fraction, ToPrimitive};
+use rustyline::error::ReadlineError;
+use rustyline::Editor;
 
-use crate::{matrix::Matrix, operations::Operations};
+use crate::{helper::CalcHelper, matrix::Matrix, operations::Operations};
 
+mod helper;
+mod lex;
 mod matrix;
 mod operations;
+mod parse;
+
+const HISTORY_FILE: &str = ".matrix_calc_history";
 
 // TODO: Refactor into lib and bin crates
 fn main() {
+    let mut editor: Editor<CalcHelper, rustyline::history::DefaultHistory> =
+        Editor::new().expect("Failed to create line editor.");
+    editor.set_helper(Some(CalcHelper::new()));
+    let _ = editor.load_history(HISTORY_FILE);
+
     'outer: loop {
-        println!(
-            "Please enter values of each row for your matrix space separeted (Empty row to stop):"
+        let mut matrix = read_matrix(
+            "Please enter values of each row for your matrix space separeted (Empty row to stop):",
         );
-        print!("> ");
-        stdout().flush().expect("Failed to flush stdout.");
-
-        let mut matrix = Matrix::new();
-
-        let mut row = Vec::new();
-        'read: while let Some(Ok(line)) = stdin().lines().next() {
-            row.clear();
-
-            if line.is_empty() {
-                println!("\nEnd of row entry.\n");
-                break 'read;
-            }
-
-            for token in line.trim().split(' ') {
-                match Fraction::from_str(token) {
-                    Ok(n) => row.push(n),
-                    Err(e) => {
-                        println!("Error: {}.", e);
-                        println!("Please ensure that the numbers are separated by only one space.");
-
-                        print!("> ");
-                        stdout().flush().expect("Failed to flush stdout.");
-                        continue 'read;
-                    }
-                }
-            }
-
-            if let Err(e) = matrix.insert_row(row.clone()) {
-                println!("Error: {}.", e);
-            }
-
-            print!("> ");
-            stdout().flush().expect("Failed to flush stdout.");
-        }
 
         if matrix.height() == 0 {
             println!("You have an empty matrix, exiting...");
@@ -63,23 +40,39 @@ fn main() {
                 .expect("Failed to convert from `Fraction` to `f64")
         );
         println!("{}\n", matrix);
-        print!("> ");
-        stdout().flush().expect("Failed to flush stdout.");
 
-        while let Some(Ok(line)) = stdin().lines().next() {
-            if line.is_empty() {
-                print!("> ");
-                stdout().flush().expect("Failed to flush stdout.");
+        if let Some(helper) = editor.helper_mut() {
+            helper.set_height(matrix.height());
+        }
+
+        loop {
+            let readline = editor.readline("> ");
+
+            let line = match readline {
+                Ok(line) => line,
+                Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => {
+                    println!("\nExiting program...");
+                    save_history(&mut editor);
+                    std::process::exit(0);
+                }
+                Err(e) => {
+                    println!("Error: {}", e);
+                    continue;
+                }
+            };
+
+            if line.trim().is_empty() {
                 continue;
             }
 
+            editor
+                .add_history_entry(line.as_str())
+                .expect("Failed to add history entry.");
+
             let op = match Operations::try_from(line.as_str()) {
                 Ok(op) => op,
                 Err(e) => {
                     println!("Error: {}", e);
-
-                    print!("> ");
-                    stdout().flush().expect("Failed to flush stdout.");
                     continue;
                 }
             };
@@ -112,21 +105,152 @@ fn main() {
                     }
                     Err(e) => println!("Error: {}", e),
                 },
+                Operations::ReduceToRref => {
+                    let steps = matrix.reduce_to_rref();
+                    if steps.is_empty() {
+                        println!("Matrix is already in RREF.");
+                    } else {
+                        for step in &steps {
+                            println!("$ {}", step);
+                        }
+                    }
+                    println!("\nMatrix (checksum: {}):", matrix.checksum());
+                    println!("\n{}\n", matrix);
+                }
+                Operations::Undo => match matrix.undo() {
+                    Ok(_) => {
+                        println!("Matrix (checksum: {}):", matrix.checksum());
+                        println!("\n{}\n", matrix);
+                    }
+                    Err(e) => println!("Error: {}", e),
+                },
+                Operations::Redo => match matrix.redo() {
+                    Ok(_) => {
+                        println!("Matrix (checksum: {}):", matrix.checksum());
+                        println!("\n{}\n", matrix);
+                    }
+                    Err(e) => println!("Error: {}", e),
+                },
+                Operations::Transpose => {
+                    matrix = matrix.transpose();
+                    if let Some(helper) = editor.helper_mut() {
+                        helper.set_height(matrix.height());
+                    }
+                    println!("Matrix (checksum: {}):", matrix.checksum());
+                    println!("\n{}\n", matrix);
+                }
+                Operations::MultiplyMatrices => {
+                    let rhs = read_matrix("Enter the right-hand matrix (Empty row to stop):");
+                    match matrix.mul(&rhs) {
+                        Ok(product) => {
+                            matrix = product;
+                            if let Some(helper) = editor.helper_mut() {
+                                helper.set_height(matrix.height());
+                            }
+                            println!("Matrix (checksum: {}):", matrix.checksum());
+                            println!("\n{}\n", matrix);
+                        }
+                        Err(e) => println!("Error: {}", e),
+                    }
+                }
+                Operations::Determinant => match matrix.determinant() {
+                    Ok(det) => println!("det = {}\n", det),
+                    Err(e) => println!("Error: {}", e),
+                },
+                Operations::Inverse => match matrix.inverse() {
+                    Ok(inverse) => {
+                        println!("Inverse (checksum: {}):", inverse.checksum());
+                        println!("\n{}\n", inverse);
+                    }
+                    Err(e) => println!("Error: {}", e),
+                },
+                Operations::Save(path) => match matrix.save(&path) {
+                    Ok(_) => println!("Saved matrix to \"{}\".\n", path),
+                    Err(e) => println!("Error: {}", e),
+                },
+                Operations::Load(path) => match Matrix::load(&path) {
+                    Ok(loaded) => {
+                        matrix = loaded;
+                        if let Some(helper) = editor.helper_mut() {
+                            helper.set_height(matrix.height());
+                        }
+                        println!("Loaded matrix from \"{}\".", path);
+                        println!("Matrix (checksum: {}):", matrix.checksum());
+                        println!("\n{}\n", matrix);
+                    }
+                    Err(e) => println!("Error: {}", e),
+                },
+                Operations::Run(path) => match matrix.run_script(&path) {
+                    Ok(steps) => {
+                        for (step, checksum) in &steps {
+                            println!("$ {} (checksum: {})", step, checksum);
+                        }
+                        println!("\nMatrix (checksum: {}):", matrix.checksum());
+                        println!("\n{}\n", matrix);
+                    }
+                    Err(e) => println!("Error: {}", e),
+                },
                 Operations::ClearScreen => clear_screen(),
                 Operations::ShowMatrix => println!("{}\n", matrix),
                 Operations::Restart => {
                     clear_screen();
+                    save_history(&mut editor);
                     continue 'outer;
                 }
                 Operations::ExitProgram => {
                     println!("\nExiting program...");
+                    save_history(&mut editor);
                     std::process::exit(0);
                 }
             }
+        }
+    }
+}
+
+fn read_matrix(prompt: &str) -> Matrix {
+    println!("{}", prompt);
+    print!("> ");
+    stdout().flush().expect("Failed to flush stdout.");
 
-            print!("> ");
-            stdout().flush().expect("Failed to flush stdout.");
+    let mut matrix = Matrix::new();
+
+    let mut row = Vec::new();
+    'read: while let Some(Ok(line)) = stdin().lines().next() {
+        row.clear();
+
+        if line.is_empty() {
+            println!("\nEnd of row entry.\n");
+            break 'read;
         }
+
+        for token in line.trim().split(' ') {
+            match Fraction::from_str(token) {
+                Ok(n) => row.push(n),
+                Err(e) => {
+                    println!("Error: {}.", e);
+                    println!("Please ensure that the numbers are separated by only one space.");
+
+                    print!("> ");
+                    stdout().flush().expect("Failed to flush stdout.");
+                    continue 'read;
+                }
+            }
+        }
+
+        if let Err(e) = matrix.insert_row(row.clone()) {
+            println!("Error: {}.", e);
+        }
+
+        print!("> ");
+        stdout().flush().expect("Failed to flush stdout.");
+    }
+
+    matrix
+}
+
+fn save_history(editor: &mut Editor<CalcHelper, rustyline::history::DefaultHistory>) {
+    if let Err(e) = editor.save_history(HISTORY_FILE) {
+        println!("Warning: failed to save command history. {}", e);
     }
 }
 
@@ -138,15 +262,28 @@ VALID OPERATIONS:
 
     Swap two rows                   S (row1 index) (row2 index)
 
-    Multiple a row                  M (scaler value) (row index)
+    Multiple a row                  M (scaler expr) (row index)
+
+    Replace `target row` with       R (scaler expr) (scaler row index) (target row index)
+    the product of the `scaler
+    row` with `scaler expr`
 
-    Replace `target row` with       R (scaler value) (scaler row index) (target row index)
-    the product of the `scaler 
-    row` with `scaler value`
+    A scaler expr is any `+ - * /` arithmetic over numbers and parens,
+    e.g. "1/2 + 2/3" or "-3/4*2".
 
 VALID COMMANDS:
     Clear screen                    c or clear
     Show matrix                     show
+    Reduce to RREF                  reduce or rref
+    Undo last operation              undo
+    Redo last undone operation       redo
+    Transpose the matrix            t or transpose
+    Multiply by another matrix      mul
+    Determinant                     det or determinant
+    Inverse                         inv or inverse
+    Save matrix to a file           save (path)
+    Load matrix from a file         load (path)
+    Run a script of operations      run (path)
     Show help                       h or help
     Restart                         restart
     Exit program                    q or exit
@@ -1,4 +1,6 @@
 use std::fmt::{self, Formatter, Write};
+use std::fs;
+use std::str::FromStr;
 
 use fraction::Fraction;
 
@@ -7,6 +9,8 @@ use crate::operations::Operations;
 pub struct Matrix {
     elements: Vec<Vec<Fraction>>,
     checksum: Fraction,
+    undo_stack: Vec<Operations>,
+    redo_stack: Vec<Operations>,
 }
 
 impl fmt::Debug for Matrix {
@@ -46,7 +50,7 @@ impl PartialEq for Matrix {
             return false;
         }
 
-        return self.elements == rhs.elements;
+        self.elements == rhs.elements
     }
 }
 
@@ -55,6 +59,8 @@ impl Matrix {
         Self {
             elements: Vec::new(),
             checksum: Fraction::from(0),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
         }
     }
 
@@ -68,6 +74,8 @@ impl Matrix {
         let mut matrix = Self {
             elements: Vec::with_capacity(iter.size_hint().0),
             checksum: Fraction::from(0),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
         };
 
         for row in iter {
@@ -150,14 +158,78 @@ impl Matrix {
 
     pub fn get(&self, (x, y): (usize, usize)) -> Result<Fraction, String> {
         self.check_xy((x, y))?;
-        return Ok(self.elements[x][y]);
+        Ok(self.elements[x][y])
     }
 
     pub fn checksum(&self) -> Fraction {
         self.checksum
     }
 
+    pub fn swap_rows(&mut self, lhs: usize, rhs: usize) -> Result<(), String> {
+        self.operate(Operations::SwapRows { lhs, rhs })
+    }
+
+    pub fn multiply_row(&mut self, row: usize, scaler: Fraction) -> Result<(), String> {
+        self.operate(Operations::Multiply { row, scaler })
+    }
+
+    pub fn replace_row_with_multiple(
+        &mut self,
+        scaler: Fraction,
+        scaler_row: usize,
+        target_row: usize,
+    ) -> Result<(), String> {
+        self.operate(Operations::ReplaceWithMultiple {
+            scaler,
+            scaler_row,
+            target_row,
+        })
+    }
+
     pub fn operate(&mut self, op: Operations) -> Result<(), String> {
+        let is_mutation = matches!(
+            op,
+            Operations::SwapRows { .. }
+                | Operations::Multiply { .. }
+                | Operations::ReplaceWithMultiple { .. }
+        );
+        let undo_entry = is_mutation.then(|| op.clone());
+
+        self.apply(op)?;
+
+        if let Some(op) = undo_entry {
+            self.undo_stack.push(op);
+            self.redo_stack.clear();
+        }
+
+        Ok(())
+    }
+
+    pub fn undo(&mut self) -> Result<(), String> {
+        let op = self
+            .undo_stack
+            .pop()
+            .ok_or_else(|| "Nothing to undo.".to_string())?;
+
+        self.apply(op.clone().inverse())?;
+        self.redo_stack.push(op);
+
+        Ok(())
+    }
+
+    pub fn redo(&mut self) -> Result<(), String> {
+        let op = self
+            .redo_stack
+            .pop()
+            .ok_or_else(|| "Nothing to redo.".to_string())?;
+
+        self.apply(op.clone())?;
+        self.undo_stack.push(op);
+
+        Ok(())
+    }
+
+    fn apply(&mut self, op: Operations) -> Result<(), String> {
         match op {
             Operations::SwapRows { lhs, rhs } => {
                 if lhs >= self.height() {
@@ -218,30 +290,285 @@ impl Matrix {
             }
             Operations::ReplaceWithMultiple {
                 scaler,
-                from_row,
-                to_row,
+                scaler_row,
+                target_row,
             } => {
-                let scaler_row = self.elements[from_row]
+                let multiple = self.elements[scaler_row]
                     .iter()
                     .map(|n| n * scaler)
                     .collect::<Vec<_>>();
 
-                let len = self.elements[to_row].len();
-                for i in 0..len {
-                    let xy = (to_row, i);
-                    self.set(xy, self.get(xy)? + scaler_row[i])?;
+                for (i, m) in multiple.into_iter().enumerate() {
+                    let xy = (target_row, i);
+                    self.set(xy, self.get(xy)? + m)?;
                 }
             }
-            // Ignore
-            Operations::ShowHelp => {}
+            // Ignore: not matrix mutations.
+            Operations::ShowHelp
+            | Operations::ClearScreen
+            | Operations::ShowMatrix
+            | Operations::Restart
+            | Operations::ReduceToRref
+            | Operations::Undo
+            | Operations::Redo
+            | Operations::Transpose
+            | Operations::MultiplyMatrices
+            | Operations::Determinant
+            | Operations::Inverse
+            | Operations::Save(_)
+            | Operations::Load(_)
+            | Operations::Run(_)
+            | Operations::ExitProgram => {}
         }
 
         Ok(())
     }
 
+    /// Returns the elementary operations applied, in order, so the caller can print each step.
+    pub fn reduce_to_rref(&mut self) -> Vec<Operations> {
+        let mut ops = Vec::new();
+
+        let width = match self.width() {
+            Some(width) => width,
+            None => return ops,
+        };
+
+        let mut pivot_row = 0;
+        for col in 0..width {
+            if pivot_row >= self.height() {
+                break;
+            }
+
+            let pivot = (pivot_row..self.height()).find(|&r| self.elements[r][col] != Fraction::from(0));
+            let pivot = match pivot {
+                Some(row) => row,
+                None => continue,
+            };
+
+            if pivot != pivot_row {
+                let op = Operations::SwapRows {
+                    lhs: pivot_row,
+                    rhs: pivot,
+                };
+                self.operate(op.clone())
+                    .expect("Swap within bounds cannot fail.");
+                ops.push(op);
+            }
+
+            let pivot_value = self.elements[pivot_row][col];
+            if pivot_value != Fraction::from(1) {
+                let op = Operations::Multiply {
+                    row: pivot_row,
+                    scaler: Fraction::from(1) / pivot_value,
+                };
+                self.operate(op.clone())
+                    .expect("Multiply within bounds cannot fail.");
+                ops.push(op);
+            }
+
+            for row in 0..self.height() {
+                if row == pivot_row {
+                    continue;
+                }
+
+                let f = self.elements[row][col];
+                if f != Fraction::from(0) {
+                    let op = Operations::ReplaceWithMultiple {
+                        scaler: -f,
+                        scaler_row: pivot_row,
+                        target_row: row,
+                    };
+                    self.operate(op.clone())
+                        .expect("Replace within bounds cannot fail.");
+                    ops.push(op);
+                }
+            }
+
+            pivot_row += 1;
+        }
+
+        ops
+    }
+
+    pub fn transpose(&self) -> Matrix {
+        let height = self.height();
+        let width = self.width().unwrap_or(0);
+
+        let rows =
+            (0..width).map(|c| (0..height).map(|r| self.elements[r][c]).collect::<Vec<_>>());
+
+        Matrix::from_iter(rows).expect("Transposing a rectangular matrix cannot fail.")
+    }
+
+    pub fn mul(&self, rhs: &Matrix) -> Result<Matrix, String> {
+        let self_width = self.width().ok_or_else(|| "Left matrix is empty.".to_string())?;
+        let rhs_width = rhs.width().ok_or_else(|| "Right matrix is empty.".to_string())?;
+
+        if self_width != rhs.height() {
+            return Err(format!(
+                "Cannot multiply a {}x{} matrix by a {}x{} matrix: inner dimensions must match.",
+                self.height(),
+                self_width,
+                rhs.height(),
+                rhs_width
+            ));
+        }
+
+        let rows = (0..self.height()).map(|i| {
+            (0..rhs_width)
+                .map(|j| {
+                    (0..self_width).fold(Fraction::from(0), |acc, k| {
+                        acc + self.elements[i][k] * rhs.elements[k][j]
+                    })
+                })
+                .collect::<Vec<_>>()
+        });
+
+        Matrix::from_iter(rows)
+    }
+
+    pub fn determinant(&self) -> Result<Fraction, String> {
+        let n = self.height();
+        match self.width() {
+            Some(width) if width == n => {}
+            Some(width) => {
+                return Err(format!(
+                    "Determinant requires a square matrix. Got {}x{}.",
+                    n, width
+                ))
+            }
+            None => return Err("Matrix is empty.".to_string()),
+        }
+
+        let mut elements = self.elements.clone();
+        let mut sign = Fraction::from(1);
+
+        for col in 0..n {
+            let pivot = (col..n).find(|&r| elements[r][col] != Fraction::from(0));
+            let pivot = match pivot {
+                Some(row) => row,
+                None => return Ok(Fraction::from(0)),
+            };
+
+            if pivot != col {
+                elements.swap(pivot, col);
+                sign = -sign;
+            }
+
+            for row in (col + 1)..n {
+                let factor = elements[row][col] / elements[col][col];
+                if factor != Fraction::from(0) {
+                    let pivot_row = elements[col][col..].to_vec();
+                    for (c, pivot_value) in (col..n).zip(pivot_row) {
+                        elements[row][c] -= pivot_value * factor;
+                    }
+                }
+            }
+        }
+
+        let det = (0..n).fold(Fraction::from(1), |acc, i| acc * elements[i][i]) * sign;
+        Ok(det)
+    }
+
+    pub fn inverse(&self) -> Result<Matrix, String> {
+        let n = self.height();
+        match self.width() {
+            Some(width) if width == n => {}
+            Some(width) => {
+                return Err(format!(
+                    "Inverse requires a square matrix. Got {}x{}.",
+                    n, width
+                ))
+            }
+            None => return Err("Matrix is empty.".to_string()),
+        }
+
+        let augmented_rows = (0..n).map(|i| {
+            (0..n)
+                .map(|j| self.elements[i][j])
+                .chain((0..n).map(move |j| if i == j { Fraction::from(1) } else { Fraction::from(0) }))
+                .collect::<Vec<_>>()
+        });
+
+        let mut augmented =
+            Matrix::from_iter(augmented_rows).expect("Augmenting a square matrix cannot fail.");
+        augmented.reduce_to_rref();
+
+        for i in 0..n {
+            let expected = |j: usize| if i == j { Fraction::from(1) } else { Fraction::from(0) };
+            if (0..n).any(|j| augmented.elements[i][j] != expected(j)) {
+                return Err("Matrix is singular and has no inverse.".to_string());
+            }
+        }
+
+        let inverse_rows =
+            (0..n).map(|i| (n..2 * n).map(|j| augmented.elements[i][j]).collect::<Vec<_>>());
+
+        Matrix::from_iter(inverse_rows)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let content = self
+            .elements
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|n| n.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        fs::write(path, content).map_err(|e| format!("Failed to write \"{}\". {}", path, e))
+    }
+
+    pub fn load(path: &str) -> Result<Matrix, String> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read \"{}\". {}", path, e))?;
+
+        let mut matrix = Matrix::new();
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let mut row = Vec::new();
+            for token in line.trim().split(' ') {
+                let n = Fraction::from_str(token)
+                    .map_err(|e| format!("Line {}: failed to parse \"{}\". {}", i + 1, token, e))?;
+                row.push(n);
+            }
+
+            matrix.insert_row(row)?;
+        }
+
+        Ok(matrix)
+    }
+
+    /// Returns each applied op alongside the checksum right after it, so the caller can print each step.
+    pub fn run_script(&mut self, path: &str) -> Result<Vec<(Operations, Fraction)>, String> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read \"{}\". {}", path, e))?;
+
+        let mut steps = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let op = Operations::try_from(line)
+                .map_err(|e| format!("Line {}: {}", i + 1, e))?;
+            self.operate(op.clone())?;
+            steps.push((op, self.checksum()));
+        }
+
+        Ok(steps)
+    }
+
     /// If len == 0; returns `None`
     pub fn width(&self) -> Option<usize> {
-        Some(self.elements.get(0)?.len())
+        Some(self.elements.first()?.len())
     }
 
     pub fn height(&self) -> usize {
@@ -1,10 +1,11 @@
-use std::{
-    fmt::{self, Formatter},
-    str::FromStr,
-};
+use std::fmt::{self, Formatter};
 
 use fraction::Fraction;
 
+use crate::lex::lex;
+use crate::parse::parse;
+
+#[derive(Clone)]
 pub enum Operations {
     SwapRows {
         lhs: usize,
@@ -21,133 +22,68 @@ pub enum Operations {
     },
     ShowHelp,
     // TODO: SetValue
-    // TODO: ShowMatrix
-    // TODO: Undo
     ClearScreen,
     ShowMatrix,
+    Restart,
+    ReduceToRref,
+    Undo,
+    Redo,
+    Transpose,
+    MultiplyMatrices,
+    Determinant,
+    Inverse,
+    Save(String),
+    Load(String),
+    Run(String),
     ExitProgram,
 }
 
+impl Operations {
+    pub fn inverse(self) -> Self {
+        match self {
+            Self::SwapRows { lhs, rhs } => Self::SwapRows { lhs, rhs },
+            Self::Multiply { row, scaler } => Self::Multiply {
+                row,
+                scaler: Fraction::from(1) / scaler,
+            },
+            Self::ReplaceWithMultiple {
+                scaler,
+                scaler_row,
+                target_row,
+            } => Self::ReplaceWithMultiple {
+                scaler: -scaler,
+                scaler_row,
+                target_row,
+            },
+            other => other,
+        }
+    }
+}
+
 impl TryFrom<&str> for Operations {
     type Error = String;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let (op, rest) = match value.split_once(' ') {
-            Some(splits) => splits,
-            None => {
-                let value_lower = value.to_lowercase();
-                match value_lower.as_str() {
-                    "h" | "help" | "c" | "clear" | "q" | "exit" | "show" => (value, ""),
-                    s => return Err(format!("\"{}\" is not a complete instruction.", s)),
-                }
-            }
+        // `save`/`load`/`run` take a filesystem path as their argument,
+        // which isn't part of the arithmetic expression grammar the lexer
+        // understands, so they're parsed directly off the raw line.
+        let trimmed = value.trim_start();
+        let (command, path) = match trimmed.split_once(char::is_whitespace) {
+            Some((command, path)) => (command, path.trim()),
+            None => (trimmed, ""),
         };
-
-        match op.to_lowercase().as_str() {
-            "h" | "help" => Ok(Self::ShowHelp),
-            "c" | "clear" => Ok(Self::ClearScreen),
-            "show" => Ok(Self::ShowMatrix),
-            "q" | "exit" => Ok(Self::ExitProgram),
-            "s" => {
-                let (lhs, rhs) = if let Some(rest) = rest.split_once(' ') {
-                    rest
-                } else {
-                    return Err(format!(
-                        "Expected two space separated row indices. Got: \"{}\"",
-                        rest
-                    ));
-                };
-
-                let (lhs, rhs) = (
-                    lhs.to_lowercase()
-                        .chars()
-                        .filter(|c| *c != 'r')
-                        .collect::<String>(),
-                    rhs.to_lowercase()
-                        .chars()
-                        .filter(|c| *c != 'r')
-                        .collect::<String>(),
-                );
-
-                let (lhs, rhs) = (
-                    lhs.parse::<usize>()
-                        .map_err(|_| format!("Failed to parse \"{}\" to `usize`", lhs))?,
-                    rhs.parse::<usize>()
-                        .map_err(|_| format!("Failed to parse \"{}\" to `usize`", rhs))?,
-                );
-
-                Ok(Self::SwapRows { lhs, rhs })
-            }
-            "m" => {
-                let (scaler, row) = if let Some(rest) = rest.split_once(' ') {
-                    rest
-                } else {
-                    return Err(format!(
-                        "Expected a scaler and a row index separated by a space. Got: \"{}\"",
-                        rest
-                    ));
-                };
-
-                let scaler = Fraction::from_str(scaler)
-                    .map_err(|e| format!("Failed to parse \"{}\". {}", scaler, e))?;
-
-                let row = row
-                    .to_lowercase()
-                    .chars()
-                    .filter(|c| *c != 'r')
-                    .collect::<String>()
-                    .parse::<usize>()
-                    .map_err(|_| format!("Failed to parse \"{}\".", row))?;
-
-                Ok(Self::Multiply { row, scaler })
+        match command.to_lowercase().as_str() {
+            "save" if !path.is_empty() => return Ok(Self::Save(path.to_string())),
+            "load" if !path.is_empty() => return Ok(Self::Load(path.to_string())),
+            "run" if !path.is_empty() => return Ok(Self::Run(path.to_string())),
+            "save" | "load" | "run" => {
+                return Err(format!("\"{}\" expects a file path argument.", command))
             }
-            "r" => {
-                let (scaler, rows) = if let Some(rest) = rest.split_once(' ') {
-                    rest
-                } else {
-                    return Err(format!(
-                        "Expected a scaler and two row indices separated by spaces. Got: \"{}\"",
-                        rest
-                    ));
-                };
-
-                let (scaler_row, target_row) = if let Some(rows) = rows.split_once(' ') {
-                    rows
-                } else {
-                    return Err(format!(
-                        "Expected two row indices separated by a space. Got: \"{}\"",
-                        rows
-                    ));
-                };
-
-                let scaler = Fraction::from_str(scaler)
-                    .map_err(|e| format!("Failed to parse \"{}\". {}", scaler, e))?;
-
-                let (scaler_row, target_row) = (
-                    scaler_row
-                        .to_lowercase()
-                        .chars()
-                        .filter(|c| *c != 'r')
-                        .collect::<String>()
-                        .parse::<usize>()
-                        .map_err(|_| format!("Failed to parse \"{}\".", scaler_row))?,
-                    target_row
-                        .to_lowercase()
-                        .chars()
-                        .filter(|c| *c != 'r')
-                        .collect::<String>()
-                        .parse::<usize>()
-                        .map_err(|_| format!("Failed to parse \"{}\".", target_row))?,
-                );
-
-                Ok(Self::ReplaceWithMultiple {
-                    scaler,
-                    scaler_row,
-                    target_row,
-                })
-            }
-            _ => Err(format!("\"{}\" is not a valid operation.", op)),
+            _ => {}
         }
+
+        let tokens = lex(value).map_err(|e| e.to_string())?;
+        parse(&tokens).map_err(|e| e.to_string())
     }
 }
 
@@ -170,6 +106,17 @@ impl fmt::Display for Operations {
             ShowHelp => f.write_str("ShowHelp"),
             ClearScreen => f.write_str("Clear Screen"),
             ShowMatrix => f.write_str("Show Matrix"),
+            Restart => f.write_str("Restart"),
+            ReduceToRref => f.write_str("Reduce to RREF"),
+            Undo => f.write_str("Undo"),
+            Redo => f.write_str("Redo"),
+            Transpose => f.write_str("Transpose"),
+            MultiplyMatrices => f.write_str("Multiply Matrices"),
+            Determinant => f.write_str("Determinant"),
+            Inverse => f.write_str("Inverse"),
+            Save(path) => f.write_fmt(format_args!("Save -> \"{}\"", path)),
+            Load(path) => f.write_fmt(format_args!("Load <- \"{}\"", path)),
+            Run(path) => f.write_fmt(format_args!("Run \"{}\"", path)),
             ExitProgram => f.write_str("Exit Program"),
         }
     }
@@ -0,0 +1,235 @@
+use std::fmt::{self, Formatter};
+
+use fraction::Fraction;
+
+use crate::lex::{Token, TokenKind};
+use crate::operations::Operations;
+
+pub struct ParseError {
+    pub message: String,
+    pub span: (usize, usize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (at byte {}..{})",
+            self.message, self.span.0, self.span.1
+        )
+    }
+}
+
+pub fn parse(tokens: &[Token]) -> Result<Operations, ParseError> {
+    let mut cursor = Cursor::new(tokens);
+
+    let op = cursor.expect_ident()?;
+    match op.to_lowercase().as_str() {
+        "h" | "help" => Ok(Operations::ShowHelp),
+        "c" | "clear" => Ok(Operations::ClearScreen),
+        "show" => Ok(Operations::ShowMatrix),
+        "restart" => Ok(Operations::Restart),
+        "reduce" | "rref" => Ok(Operations::ReduceToRref),
+        "undo" => Ok(Operations::Undo),
+        "redo" => Ok(Operations::Redo),
+        "t" | "transpose" => Ok(Operations::Transpose),
+        "mul" => Ok(Operations::MultiplyMatrices),
+        "det" | "determinant" => Ok(Operations::Determinant),
+        "inv" | "inverse" => Ok(Operations::Inverse),
+        "q" | "exit" => Ok(Operations::ExitProgram),
+        "s" => {
+            let lhs = cursor.expect_row()?;
+            let rhs = cursor.expect_row()?;
+            Ok(Operations::SwapRows { lhs, rhs })
+        }
+        "m" => {
+            let scaler_start = cursor.pos;
+            let scaler = cursor.expect_expr()?;
+            if scaler == Fraction::from(0) {
+                return Err(ParseError {
+                    message: "Scaler cannot be zero".to_string(),
+                    span: cursor.span_since(scaler_start),
+                });
+            }
+            let row = cursor.expect_row()?;
+            Ok(Operations::Multiply { row, scaler })
+        }
+        "r" => {
+            let scaler = cursor.expect_expr()?;
+            let scaler_row = cursor.expect_row()?;
+            let target_row = cursor.expect_row()?;
+            Ok(Operations::ReplaceWithMultiple {
+                scaler,
+                scaler_row,
+                target_row,
+            })
+        }
+        other => Err(ParseError {
+            message: format!("\"{}\" is not a valid operation", other),
+            span: tokens[0].span,
+        }),
+    }
+}
+
+struct Cursor<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(tokens: &'a [Token]) -> Self {
+        Self { tokens, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn end_span(&self) -> (usize, usize) {
+        self.tokens
+            .last()
+            .map(|t| (t.span.1, t.span.1))
+            .unwrap_or((0, 0))
+    }
+
+    fn span_since(&self, start_pos: usize) -> (usize, usize) {
+        let start = self.tokens[start_pos].span.0;
+        let end = self.tokens[self.pos - 1].span.1;
+        (start, end)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let tok = self.tokens.get(self.pos);
+        if tok.is_some() {
+            self.pos += 1;
+        }
+        tok
+    }
+
+    fn expect_ident(&mut self) -> Result<String, ParseError> {
+        match self.next() {
+            Some(Token {
+                kind: TokenKind::Ident(s),
+                ..
+            }) => Ok(s.clone()),
+            Some(tok) => Err(ParseError {
+                message: "Expected a command".to_string(),
+                span: tok.span,
+            }),
+            None => Err(ParseError {
+                message: "Expected a command, got an empty line".to_string(),
+                span: (0, 0),
+            }),
+        }
+    }
+
+    fn expect_row(&mut self) -> Result<usize, ParseError> {
+        match self.next() {
+            Some(Token {
+                kind: TokenKind::Row(n),
+                ..
+            }) => Ok(*n),
+            Some(Token {
+                kind: TokenKind::Number(n),
+                ..
+            }) if *n >= 0 => Ok(*n as usize),
+            Some(tok) => Err(ParseError {
+                message: "Expected a row reference, e.g. \"r1\"".to_string(),
+                span: tok.span,
+            }),
+            None => Err(ParseError {
+                message: "Expected a row reference, got an incomplete instruction".to_string(),
+                span: self.end_span(),
+            }),
+        }
+    }
+
+    fn expect_expr(&mut self) -> Result<Fraction, ParseError> {
+        let mut lhs = self.expect_term()?;
+
+        loop {
+            match self.peek().map(|t| &t.kind) {
+                Some(TokenKind::Plus) => {
+                    self.next();
+                    lhs += self.expect_term()?;
+                }
+                Some(TokenKind::Minus) => {
+                    self.next();
+                    lhs -= self.expect_term()?;
+                }
+                _ => break,
+            }
+        }
+
+        Ok(lhs)
+    }
+
+    fn expect_term(&mut self) -> Result<Fraction, ParseError> {
+        let mut lhs = self.expect_atom()?;
+
+        loop {
+            match self.peek().map(|t| &t.kind) {
+                Some(TokenKind::Star) => {
+                    self.next();
+                    lhs *= self.expect_atom()?;
+                }
+                Some(TokenKind::Slash) => {
+                    let slash_span = self.next().unwrap().span;
+                    let rhs = self.expect_atom()?;
+                    if rhs == Fraction::from(0) {
+                        return Err(ParseError {
+                            message: "Division by zero".to_string(),
+                            span: slash_span,
+                        });
+                    }
+                    lhs /= rhs;
+                }
+                _ => break,
+            }
+        }
+
+        Ok(lhs)
+    }
+
+    fn expect_atom(&mut self) -> Result<Fraction, ParseError> {
+        match self.next() {
+            Some(Token {
+                kind: TokenKind::Number(n),
+                ..
+            }) => Ok(Fraction::from(*n)),
+            Some(Token {
+                kind: TokenKind::Minus,
+                ..
+            }) => Ok(-self.expect_atom()?),
+            Some(Token {
+                kind: TokenKind::LParen,
+                ..
+            }) => {
+                let inner = self.expect_expr()?;
+                match self.next() {
+                    Some(Token {
+                        kind: TokenKind::RParen,
+                        ..
+                    }) => Ok(inner),
+                    Some(tok) => Err(ParseError {
+                        message: "Expected a closing \")\"".to_string(),
+                        span: tok.span,
+                    }),
+                    None => Err(ParseError {
+                        message: "Expected a closing \")\", got an incomplete instruction"
+                            .to_string(),
+                        span: self.end_span(),
+                    }),
+                }
+            }
+            Some(tok) => Err(ParseError {
+                message: "Expected a number, \"-\", or \"(\"".to_string(),
+                span: tok.span,
+            }),
+            None => Err(ParseError {
+                message: "Expected a scaler, got an incomplete instruction".to_string(),
+                span: self.end_span(),
+            }),
+        }
+    }
+}